@@ -0,0 +1,3 @@
+//! Reusable helpers layered on top of the WiFi stack.
+
+pub mod captive_portal;
@@ -0,0 +1,297 @@
+//! Minimal DHCP server and captive-portal DNS responder for the AP interface.
+//!
+//! These are meant to be driven as background tasks next to an access-point
+//! `embassy_net::Stack`. The [`DhcpServer`] hands out leases from a configurable
+//! pool so clients join with zero manual IP setup, and [`CaptivePortalDns`]
+//! answers every A query with the AP address so the first browser request lands
+//! on the local HTTP page (the WiFiManager captive-portal pattern).
+//!
+//! Both operate on `embassy_net::udp::UdpSocket`s bound to the AP stack.
+
+use embassy_net::{udp::UdpSocket, IpEndpoint, Ipv4Address};
+
+/// Address pool and network parameters handed out by the [`DhcpServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DhcpServerConfig {
+    /// Address of the AP itself; used as gateway and DNS server.
+    pub server: Ipv4Address,
+    /// Subnet mask offered to clients.
+    pub netmask: Ipv4Address,
+    /// First address of the lease pool (inclusive).
+    pub pool_start: Ipv4Address,
+    /// Last address of the lease pool (inclusive).
+    pub pool_end: Ipv4Address,
+    /// Lease time offered to clients, in seconds.
+    pub lease_secs: u32,
+}
+
+impl Default for DhcpServerConfig {
+    /// Defaults matching the `access_point` example: gateway 192.168.2.1 with
+    /// a 192.168.2.2 .. 192.168.2.254 pool.
+    fn default() -> Self {
+        Self {
+            server: Ipv4Address::new(192, 168, 2, 1),
+            netmask: Ipv4Address::new(255, 255, 255, 0),
+            pool_start: Ipv4Address::new(192, 168, 2, 2),
+            pool_end: Ipv4Address::new(192, 168, 2, 254),
+            lease_secs: 7200,
+        }
+    }
+}
+
+/// DHCP message type option values (option 53).
+const DHCP_DISCOVER: u8 = 1;
+const DHCP_OFFER: u8 = 2;
+const DHCP_REQUEST: u8 = 3;
+const DHCP_ACK: u8 = 5;
+const DHCP_MAGIC: [u8; 4] = [99, 130, 83, 99];
+
+/// A tiny single-lease-at-a-time DHCP server.
+///
+/// It answers `DISCOVER` with an `OFFER` and `REQUEST` with an `ACK`, leasing
+/// the next address in the pool. It intentionally keeps no persistent
+/// lease table beyond a rolling cursor - adequate for a handful of captive
+/// clients on an AP.
+pub struct DhcpServer {
+    config: DhcpServerConfig,
+    next: u8,
+    last_offered: Ipv4Address,
+}
+
+impl DhcpServer {
+    /// Create a server for the given pool.
+    pub fn new(config: DhcpServerConfig) -> Self {
+        let next = config.pool_start.octets()[3];
+        Self {
+            config,
+            next,
+            last_offered: config.pool_start,
+        }
+    }
+
+    /// Serve DHCP on `socket` forever. The socket must already be bound to
+    /// UDP port 67 on the AP stack.
+    pub async fn run(&mut self, socket: &mut UdpSocket<'_>) -> ! {
+        let mut buf = [0u8; 576];
+        loop {
+            let Ok((len, _meta)) = socket.recv_from(&mut buf).await else {
+                continue;
+            };
+            if let Some(reply_len) = self.handle(&mut buf, len) {
+                let broadcast = IpEndpoint::new(Ipv4Address::BROADCAST.into(), 68);
+                let _ = socket.send_to(&buf[..reply_len], broadcast).await;
+            }
+        }
+    }
+
+    /// Build a reply in-place, returning its length, or `None` to drop the
+    /// packet.
+    fn handle(&mut self, buf: &mut [u8], len: usize) -> Option<usize> {
+        // BOOTP fixed header is 236 bytes, followed by the magic cookie.
+        if len < 240 || buf[0] != 1 || buf[240 - 4..240] != DHCP_MAGIC {
+            return None;
+        }
+
+        let options = &buf[240..len];
+        let message_type = find_option(options, 53)?.first().copied()?;
+        // Only the DISCOVER consumes a pool address; the REQUEST must confirm
+        // that same address or the client rejects the ACK.
+        let (reply_type, offered) = match message_type {
+            DHCP_DISCOVER => (DHCP_OFFER, self.lease()),
+            DHCP_REQUEST => {
+                let requested = find_option(options, 50)
+                    .and_then(|v| v.try_into().ok())
+                    .map(|o: [u8; 4]| Ipv4Address::from_bytes(&o))
+                    .unwrap_or(self.last_offered);
+                (DHCP_ACK, requested)
+            }
+            _ => return None,
+        };
+
+        buf[0] = 2; // BOOTREPLY
+        // yiaddr - the address offered to the client.
+        buf[16..20].copy_from_slice(&offered.octets());
+        // siaddr - next server (us).
+        buf[20..24].copy_from_slice(&self.config.server.octets());
+
+        let mut pos = 240;
+        pos += write_option(&mut buf[pos..], 53, &[reply_type]);
+        pos += write_option(&mut buf[pos..], 54, &self.config.server.octets());
+        pos += write_option(&mut buf[pos..], 51, &self.config.lease_secs.to_be_bytes());
+        pos += write_option(&mut buf[pos..], 1, &self.config.netmask.octets());
+        pos += write_option(&mut buf[pos..], 3, &self.config.server.octets());
+        pos += write_option(&mut buf[pos..], 6, &self.config.server.octets());
+        buf[pos] = 255; // END
+        pos += 1;
+
+        Some(pos)
+    }
+
+    /// Advance the pool cursor and return the next address to offer.
+    fn lease(&mut self) -> Ipv4Address {
+        let start = self.config.pool_start.octets()[3];
+        let end = self.config.pool_end.octets()[3];
+        let host = self.next;
+        self.next = if self.next >= end { start } else { self.next + 1 };
+        let mut octets = self.config.server.octets();
+        octets[3] = host;
+        let offered = Ipv4Address::from_bytes(&octets);
+        self.last_offered = offered;
+        offered
+    }
+}
+
+/// Return the value of DHCP option `code`, skipping PAD and stopping at END.
+fn find_option(mut opts: &[u8], code: u8) -> Option<&[u8]> {
+    while let Some((&opt, rest)) = opts.split_first() {
+        match opt {
+            255 => break,     // END
+            0 => opts = rest, // PAD
+            _ => {
+                let (&len, rest) = rest.split_first()?;
+                let (value, rest) = rest.split_at_checked(len as usize)?;
+                if opt == code {
+                    return Some(value);
+                }
+                opts = rest;
+            }
+        }
+    }
+    None
+}
+
+/// Append a DHCP option `code` with `value`, returning the bytes written.
+fn write_option(buf: &mut [u8], code: u8, value: &[u8]) -> usize {
+    buf[0] = code;
+    buf[1] = value.len() as u8;
+    buf[2..2 + value.len()].copy_from_slice(value);
+    2 + value.len()
+}
+
+/// A captive-portal DNS responder that answers every `A` query with the AP
+/// address, so any hostname a client resolves points back at the portal.
+pub struct CaptivePortalDns {
+    server: Ipv4Address,
+}
+
+impl CaptivePortalDns {
+    /// Create a responder that resolves every name to `server`.
+    pub fn new(server: Ipv4Address) -> Self {
+        Self { server }
+    }
+
+    /// Serve DNS on `socket` forever. The socket must already be bound to UDP
+    /// port 53 on the AP stack.
+    pub async fn run(&self, socket: &mut UdpSocket<'_>) -> ! {
+        let mut buf = [0u8; 512];
+        loop {
+            let Ok((len, meta)) = socket.recv_from(&mut buf).await else {
+                continue;
+            };
+            if let Some(reply_len) = self.handle(&mut buf, len) {
+                let _ = socket.send_to(&buf[..reply_len], meta.endpoint).await;
+            }
+        }
+    }
+
+    /// DNS QTYPE for an IPv4 address record.
+    const QTYPE_A: u16 = 1;
+
+    /// Turn the query in `buf` into an answer pointing at `self.server`.
+    fn handle(&self, buf: &mut [u8], len: usize) -> Option<usize> {
+        // Header is 12 bytes; require exactly one question.
+        if len < 12 || u16::from_be_bytes([buf[4], buf[5]]) != 1 {
+            return None;
+        }
+
+        // Walk the QNAME labels (terminated by a zero length octet), staying
+        // within the received datagram.
+        let mut pos = 12;
+        while pos < len && buf[pos] != 0 {
+            pos += buf[pos] as usize + 1;
+        }
+        // Require the terminator and the QTYPE+QCLASS that follow it.
+        if pos >= len || pos + 5 > len {
+            return None;
+        }
+        let qtype = u16::from_be_bytes([buf[pos + 1], buf[pos + 2]]);
+        let question_end = pos + 5; // null label + QTYPE + QCLASS
+
+        // Flags: set QR (response), RD and RA, clear everything else.
+        buf[2] = 0x81;
+        buf[3] = 0x80;
+
+        // Only A queries get a synthesized address; anything else (AAAA, MX,
+        // TXT, ...) is answered with zero records so we never hand back a bogus
+        // A record; an A query whose answer would not fit in the buffer is
+        // answered the same way.
+        if qtype != Self::QTYPE_A || question_end + 16 > buf.len() {
+            buf[6..8].copy_from_slice(&0u16.to_be_bytes());
+            return Some(question_end);
+        }
+
+        // One answer, matching the single question.
+        buf[6..8].copy_from_slice(&1u16.to_be_bytes());
+
+        // Append the answer record after the question.
+        let mut pos = question_end;
+        buf[pos..pos + 2].copy_from_slice(&0xC00Cu16.to_be_bytes()); // name pointer -> question
+        pos += 2;
+        buf[pos..pos + 2].copy_from_slice(&Self::QTYPE_A.to_be_bytes()); // TYPE A
+        pos += 2;
+        buf[pos..pos + 2].copy_from_slice(&1u16.to_be_bytes()); // CLASS IN
+        pos += 2;
+        buf[pos..pos + 4].copy_from_slice(&60u32.to_be_bytes()); // TTL
+        pos += 4;
+        buf[pos..pos + 2].copy_from_slice(&4u16.to_be_bytes()); // RDLENGTH
+        pos += 2;
+        buf[pos..pos + 4].copy_from_slice(&self.server.octets());
+        pos += 4;
+
+        Some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_then_find_option_round_trips() {
+        let mut buf = [0u8; 16];
+        let written = write_option(&mut buf, 53, &[DHCP_DISCOVER]);
+        assert_eq!(written, 3);
+        // A trailing END so the walk terminates cleanly.
+        buf[written] = 255;
+        assert_eq!(find_option(&buf, 53), Some(&[DHCP_DISCOVER][..]));
+        // An absent option is reported as missing.
+        assert_eq!(find_option(&buf, 50), None);
+    }
+
+    #[test]
+    fn find_option_skips_pad_and_stops_at_end() {
+        // PAD, option 50 = 1.2.3.4, END, then trailing garbage after END.
+        let opts = [0, 50, 4, 1, 2, 3, 4, 255, 99, 99];
+        assert_eq!(find_option(&opts, 50), Some(&[1, 2, 3, 4][..]));
+        // Options past END are not visible.
+        assert_eq!(find_option(&opts, 99), None);
+    }
+
+    #[test]
+    fn lease_walks_pool_and_wraps() {
+        let mut server = DhcpServer::new(DhcpServerConfig {
+            server: Ipv4Address::new(192, 168, 2, 1),
+            netmask: Ipv4Address::new(255, 255, 255, 0),
+            pool_start: Ipv4Address::new(192, 168, 2, 2),
+            pool_end: Ipv4Address::new(192, 168, 2, 4),
+            lease_secs: 60,
+        });
+
+        assert_eq!(server.lease(), Ipv4Address::new(192, 168, 2, 2));
+        assert_eq!(server.lease(), Ipv4Address::new(192, 168, 2, 3));
+        assert_eq!(server.lease(), Ipv4Address::new(192, 168, 2, 4));
+        // The cursor wraps back to the start of the pool.
+        assert_eq!(server.lease(), Ipv4Address::new(192, 168, 2, 2));
+        assert_eq!(server.last_offered, Ipv4Address::new(192, 168, 2, 2));
+    }
+}
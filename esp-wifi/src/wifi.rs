@@ -0,0 +1,34 @@
+//! WiFi driver and configuration.
+//!
+//! Only the additions made by this backlog are shown here; the surrounding
+//! `WifiController`, `WifiError`, `WifiEvent` and `Configuration` definitions
+//! live in the rest of this module.
+
+mod background_scan;
+mod enterprise;
+mod ftm;
+mod geolocation;
+mod link_stats;
+mod twt;
+
+pub use background_scan::{
+    BackgroundScanConfig,
+    BackgroundScanEvent,
+    BackgroundScanner,
+    CachedScanResult,
+    ScanBucket,
+    MAX_SCAN_BUCKETS,
+};
+pub use enterprise::{
+    ClientCertificate,
+    EapMethod,
+    EnterpriseClientConfiguration,
+};
+pub use ftm::{FtmConfig, FtmReport, FtmSample};
+pub use geolocation::{
+    collect_geolocation_fingerprint,
+    FingerprintFilter,
+    GeolocationFingerprint,
+};
+pub use link_stats::LinkLayerStats;
+pub use twt::{TwtConfiguration, TwtEvent, TwtFlowId, TwtTriggerMode};
@@ -0,0 +1,312 @@
+//! Scheduled background scanning with a result cache and change events.
+//!
+//! Unlike the one-shot [`scan`](super::WifiController::scan_n) call, a
+//! background scan runs on a timer without tearing down connectivity,
+//! accumulating what it sees into a bounded cache.
+//! [`WifiController::start_background_scan`] configures per-channel buckets and
+//! returns a [`BackgroundScanner`]; results are drained with
+//! [`BackgroundScanner::get_cached_scan_results`] in response to the
+//! [`BackgroundScanEvent`]s yielded by
+//! [`BackgroundScanner::run_until_event`].
+
+use alloc::vec::Vec;
+
+use super::{WifiController, WifiError};
+
+/// Maximum number of channel buckets a background scan can define.
+pub const MAX_SCAN_BUCKETS: usize = 8;
+
+/// A group of channels scanned together on a shared period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScanBucket {
+    /// Channels covered by this bucket.
+    pub channels: &'static [u8],
+    /// How often the bucket is scanned, in milliseconds.
+    pub period_ms: u32,
+    /// Maximum number of APs cached from this bucket per pass.
+    pub max_aps: u8,
+}
+
+/// Configuration for a background scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackgroundScanConfig {
+    /// Channel buckets to cycle through.
+    pub buckets: Vec<ScanBucket>,
+    /// Total capacity of the result cache.
+    pub cache_size: usize,
+    /// Number of newly-seen BSSIDs that should raise
+    /// [`BackgroundScanEvent::ResultsAvailable`].
+    pub report_threshold: usize,
+}
+
+/// One cached observation of an access point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CachedScanResult {
+    /// BSSID of the access point.
+    pub bssid: [u8; 6],
+    /// SSID, empty for hidden networks.
+    pub ssid: heapless::String<32>,
+    /// Last observed signal strength, in dBm.
+    pub rssi: i8,
+    /// Channel the AP was last heard on.
+    pub channel: u8,
+    /// Timestamp of the last observation, in milliseconds since boot.
+    pub last_seen_ms: u64,
+}
+
+/// Outcome of folding a single observation into the [`ScanCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Record {
+    /// A BSSID not previously cached was inserted.
+    New,
+    /// An existing BSSID was refreshed in place.
+    Updated,
+    /// The cache was full and the new BSSID was dropped.
+    Rejected,
+}
+
+/// Fixed-capacity cache of [`CachedScanResult`]s.
+///
+/// Observations of a BSSID already present are updated in place so the cache
+/// tracks the freshest view of each AP. Once full it keeps its contents and
+/// rejects new BSSIDs rather than evicting, so nothing is lost before the host
+/// drains it.
+pub(crate) struct ScanCache {
+    entries: Vec<CachedScanResult>,
+    capacity: usize,
+}
+
+impl ScanCache {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Insert or refresh `result`, reporting what happened.
+    pub(crate) fn record(&mut self, result: CachedScanResult) -> Record {
+        if let Some(existing) = self.entries.iter_mut().find(|e| e.bssid == result.bssid) {
+            *existing = result;
+            return Record::Updated;
+        }
+
+        if self.entries.len() < self.capacity {
+            self.entries.push(result);
+            Record::New
+        } else {
+            Record::Rejected
+        }
+    }
+
+    pub(crate) fn drain(&mut self) -> Vec<CachedScanResult> {
+        core::mem::take(&mut self.entries)
+    }
+}
+
+/// A change in the background scan cache worth waking the host for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BackgroundScanEvent {
+    /// At least `report_threshold` new BSSIDs have been seen since the last
+    /// report. Drain with
+    /// [`get_cached_scan_results`](BackgroundScanner::get_cached_scan_results).
+    ResultsAvailable,
+    /// The result cache just reached capacity and is now rejecting new BSSIDs;
+    /// drain it to resume caching. Fired once per fill, on the edge.
+    CacheFull,
+}
+
+/// A running background scanner.
+///
+/// Created by [`WifiController::start_background_scan`]. Drive it from an async
+/// task with [`run_until_event`](Self::run_until_event), draining the cache
+/// with [`get_cached_scan_results`](Self::get_cached_scan_results) whenever it
+/// yields an event. It schedules each bucket on its own period and never tears
+/// down an active connection.
+pub struct BackgroundScanner {
+    config: BackgroundScanConfig,
+    cache: ScanCache,
+    next_due_ms: [u64; MAX_SCAN_BUCKETS],
+    new_since_report: usize,
+    reported_full: bool,
+}
+
+impl BackgroundScanner {
+    fn new(config: BackgroundScanConfig) -> Self {
+        let cache = ScanCache::with_capacity(config.cache_size);
+        Self {
+            config,
+            cache,
+            next_due_ms: [0; MAX_SCAN_BUCKETS],
+            new_since_report: 0,
+            reported_full: false,
+        }
+    }
+
+    /// Return the index of a bucket due at `now_ms`, advancing its schedule by
+    /// its own `period_ms`; otherwise the absolute time the next bucket is due.
+    fn next_bucket(&mut self, now_ms: u64) -> Result<usize, u64> {
+        let mut soonest = u64::MAX;
+        for (idx, bucket) in self.config.buckets.iter().enumerate() {
+            if self.next_due_ms[idx] <= now_ms {
+                self.next_due_ms[idx] = now_ms + bucket.period_ms as u64;
+                return Ok(idx);
+            }
+            soonest = soonest.min(self.next_due_ms[idx]);
+        }
+        Err(soonest)
+    }
+
+    /// Fold a bucket's scan results into the cache, capping at the bucket's
+    /// `max_aps` and returning an event once a threshold is crossed.
+    ///
+    /// `CacheFull` is raised on the edge - only the first time a new BSSID is
+    /// rejected because the cache is full, and not again until a drain frees
+    /// space. The `report_threshold` path is evaluated first, so it stays
+    /// reachable right up to the fill; when both are crossed in the same pass,
+    /// `ResultsAvailable` is returned and `CacheFull` is raised at the next
+    /// rejection instead.
+    fn ingest(
+        &mut self,
+        bucket: usize,
+        results: &[CachedScanResult],
+    ) -> Option<BackgroundScanEvent> {
+        let max_aps = self.config.buckets[bucket].max_aps as usize;
+        let mut just_filled = false;
+        for result in results.iter().take(max_aps) {
+            match self.cache.record(result.clone()) {
+                Record::New => self.new_since_report += 1,
+                Record::Rejected if !self.reported_full => just_filled = true,
+                Record::Rejected | Record::Updated => {}
+            }
+        }
+
+        if self.new_since_report >= self.config.report_threshold {
+            self.new_since_report = 0;
+            return Some(BackgroundScanEvent::ResultsAvailable);
+        }
+        if just_filled {
+            self.reported_full = true;
+            return Some(BackgroundScanEvent::CacheFull);
+        }
+        None
+    }
+
+    /// Scan due buckets on their schedule until a [`BackgroundScanEvent`] is
+    /// raised, then return it. Call in a loop, draining the cache after each
+    /// event.
+    pub async fn run_until_event(
+        &mut self,
+        controller: &mut WifiController<'_>,
+    ) -> Result<BackgroundScanEvent, WifiError> {
+        loop {
+            let now_ms = embassy_time::Instant::now().as_millis();
+            let bucket = match self.next_bucket(now_ms) {
+                Ok(bucket) => bucket,
+                Err(due_ms) => {
+                    embassy_time::Timer::after(embassy_time::Duration::from_millis(
+                        due_ms.saturating_sub(now_ms),
+                    ))
+                    .await;
+                    continue;
+                }
+            };
+
+            let seen = self.scan_bucket(controller, bucket).await?;
+            if let Some(event) = self.ingest(bucket, &seen) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Scan every channel in a bucket and collect the observations.
+    async fn scan_bucket(
+        &self,
+        controller: &mut WifiController<'_>,
+        bucket: usize,
+    ) -> Result<Vec<CachedScanResult>, WifiError> {
+        let now_ms = embassy_time::Instant::now().as_millis();
+        let mut seen = Vec::new();
+        for &channel in self.config.buckets[bucket].channels {
+            let config = super::ScanConfig {
+                channel: Some(channel),
+                show_hidden: true,
+                ..Default::default()
+            };
+            let (found, _count) = controller.scan_with_config::<16>(config).await?;
+            for ap in found {
+                seen.push(CachedScanResult {
+                    bssid: ap.bssid,
+                    ssid: ap.ssid,
+                    rssi: ap.signal_strength,
+                    channel: ap.channel,
+                    last_seen_ms: now_ms,
+                });
+            }
+        }
+        Ok(seen)
+    }
+
+    /// Drain the accumulated scan cache, freeing space so caching resumes and
+    /// a later fill can raise [`BackgroundScanEvent::CacheFull`] again.
+    pub fn get_cached_scan_results(&mut self) -> Vec<CachedScanResult> {
+        self.reported_full = false;
+        self.cache.drain()
+    }
+}
+
+impl WifiController<'_> {
+    /// Start a background scan with the given bucket schedule, returning a
+    /// [`BackgroundScanner`] to drive it. Scanning does not interrupt an active
+    /// connection.
+    pub fn start_background_scan(
+        &mut self,
+        config: BackgroundScanConfig,
+    ) -> Result<BackgroundScanner, WifiError> {
+        if config.buckets.is_empty() || config.buckets.len() > MAX_SCAN_BUCKETS {
+            return Err(WifiError::InvalidArguments);
+        }
+        Ok(BackgroundScanner::new(config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(bssid_last: u8) -> CachedScanResult {
+        CachedScanResult {
+            bssid: [0, 0, 0, 0, 0, bssid_last],
+            ssid: heapless::String::new(),
+            rssi: -50,
+            channel: 1,
+            last_seen_ms: 0,
+        }
+    }
+
+    #[test]
+    fn record_reports_new_updated_and_rejected() {
+        let mut cache = ScanCache::with_capacity(2);
+        assert_eq!(cache.record(result(1)), Record::New);
+        assert_eq!(cache.record(result(2)), Record::New);
+        // Refreshing a known BSSID updates in place without growing the cache.
+        assert_eq!(cache.record(result(1)), Record::Updated);
+        // A new BSSID is rejected once full; nothing is evicted.
+        assert_eq!(cache.record(result(3)), Record::Rejected);
+    }
+
+    #[test]
+    fn drain_empties_and_refreshes_capacity() {
+        let mut cache = ScanCache::with_capacity(2);
+        cache.record(result(1));
+        cache.record(result(2));
+
+        let drained = cache.drain();
+        assert_eq!(drained.len(), 2);
+        // Space is freed, so the next BSSID is accepted again.
+        assert_eq!(cache.record(result(3)), Record::New);
+    }
+}
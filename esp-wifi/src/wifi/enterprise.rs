@@ -0,0 +1,185 @@
+//! WPA2/WPA3-Enterprise (802.1X / EAP) station configuration.
+//!
+//! [`WifiController::set_enterprise_configuration`] pushes the enrollment
+//! parameters in an [`EnterpriseClientConfiguration`] into the supplicant so a
+//! station can join 802.1X protected networks (the typical eduroam-style
+//! deployment). Call it in place of
+//! [`set_configuration`](super::WifiController::set_configuration) before
+//! [`connect`](super::WifiController::connect); it is the pure-Rust equivalent
+//! of the `wifi_connect_ent(ssid, ident, anon_ident, password)` entry point
+//! IDF apps expose.
+
+use super::{WifiController, WifiError};
+
+/// EAP method used for the outer authentication exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EapMethod {
+    /// Protected EAP - credentials are tunnelled inside TLS.
+    Peap,
+    /// Tunnelled TLS - like PEAP but allows legacy inner methods.
+    Ttls,
+    /// EAP-TLS - mutual certificate authentication, no password.
+    Tls,
+}
+
+/// A PEM/DER encoded certificate or key blob kept alive for the duration of
+/// the association.
+///
+/// The blobs are handed to the supplicant by
+/// [`set_enterprise_configuration`](super::WifiController::set_enterprise_configuration).
+pub type CertBlob<'a> = &'a [u8];
+
+/// Client certificate paired with its private key, used by EAP-TLS and for the
+/// optional client authentication of PEAP/TTLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ClientCertificate<'a> {
+    /// The client certificate blob.
+    pub certificate: CertBlob<'a>,
+    /// The matching private key blob.
+    pub private_key: CertBlob<'a>,
+    /// Optional passphrase protecting `private_key`.
+    pub private_key_password: Option<&'a str>,
+}
+
+/// Configuration for joining a WPA2/WPA3-Enterprise network.
+///
+/// `identity` is the outer/anonymous identity sent in the clear (for example
+/// `anonymous@example.edu`), while `username` is the inner identity revealed
+/// only inside the TLS tunnel. For [`EapMethod::Tls`] the password is ignored
+/// and a [`ClientCertificate`] is required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EnterpriseClientConfiguration<'a> {
+    /// The SSID of the enterprise network.
+    pub ssid: heapless::String<32>,
+    /// EAP method used for the outer exchange.
+    pub method: EapMethod,
+    /// Outer/anonymous identity sent before the tunnel is established.
+    pub identity: heapless::String<128>,
+    /// Inner identity (username) authenticated inside the tunnel.
+    pub username: heapless::String<128>,
+    /// Password for the inner method. Unused for [`EapMethod::Tls`].
+    pub password: heapless::String<128>,
+    /// Optional CA certificate used to validate the server.
+    pub ca_cert: Option<CertBlob<'a>>,
+    /// Optional client certificate + key for mutual authentication.
+    pub client_cert: Option<ClientCertificate<'a>>,
+}
+
+impl Default for EnterpriseClientConfiguration<'_> {
+    fn default() -> Self {
+        Self {
+            ssid: heapless::String::new(),
+            method: EapMethod::Peap,
+            identity: heapless::String::new(),
+            username: heapless::String::new(),
+            password: heapless::String::new(),
+            ca_cert: None,
+            client_cert: None,
+        }
+    }
+}
+
+impl EnterpriseClientConfiguration<'_> {
+    /// Validate the combination of method and supplied credentials.
+    pub(crate) fn validate(&self) -> Result<(), WifiError> {
+        match self.method {
+            EapMethod::Tls if self.client_cert.is_none() => {
+                Err(WifiError::InvalidArguments)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl WifiController<'_> {
+    /// Configure the station interface to join a WPA2/WPA3-Enterprise network.
+    ///
+    /// Sets the SSID as a plain client configuration and then runs the EAP
+    /// enrollment sequence, so the caller only needs to
+    /// [`connect`](Self::connect) afterwards. It mirrors the
+    /// `wifi_connect_ent(ssid, ident, anon_ident, password)` entry point IDF
+    /// apps expose.
+    pub fn set_enterprise_configuration(
+        &mut self,
+        config: &EnterpriseClientConfiguration<'_>,
+    ) -> Result<(), WifiError> {
+        config.validate()?;
+
+        let mut client = super::ClientConfiguration::default();
+        client.ssid = config.ssid.clone();
+        client.auth_method = super::AuthMethod::WPA2Enterprise;
+        self.set_configuration(&super::Configuration::Client(client))?;
+
+        self.enroll_enterprise(config)
+    }
+
+    /// Push the enterprise enrollment parameters into the supplicant.
+    fn enroll_enterprise(
+        &mut self,
+        config: &EnterpriseClientConfiguration<'_>,
+    ) -> Result<(), WifiError> {
+        unsafe {
+            esp_wifi_result!(include::esp_eap_client_set_identity(
+                config.identity.as_bytes().as_ptr(),
+                config.identity.len() as i32,
+            ))?;
+
+            if !config.username.is_empty() {
+                esp_wifi_result!(include::esp_eap_client_set_username(
+                    config.username.as_bytes().as_ptr(),
+                    config.username.len() as i32,
+                ))?;
+            }
+
+            if !config.password.is_empty() {
+                esp_wifi_result!(include::esp_eap_client_set_password(
+                    config.password.as_bytes().as_ptr(),
+                    config.password.len() as i32,
+                ))?;
+            }
+
+            if let Some(ca) = config.ca_cert {
+                esp_wifi_result!(include::esp_eap_client_set_ca_cert(
+                    ca.as_ptr(),
+                    ca.len() as i32,
+                ))?;
+            }
+
+            if let Some(cert) = &config.client_cert {
+                let (pwd_ptr, pwd_len) = match cert.private_key_password {
+                    Some(p) => (p.as_bytes().as_ptr(), p.len() as i32),
+                    None => (core::ptr::null(), 0),
+                };
+                esp_wifi_result!(include::esp_eap_client_set_certificate_and_key(
+                    cert.certificate.as_ptr(),
+                    cert.certificate.len() as i32,
+                    cert.private_key.as_ptr(),
+                    cert.private_key.len() as i32,
+                    pwd_ptr,
+                    pwd_len,
+                ))?;
+            }
+
+            match config.method {
+                // TTLS tunnels a legacy inner method; select MSCHAPv2.
+                EapMethod::Ttls => {
+                    esp_wifi_result!(include::esp_eap_client_set_ttls_phase2_method(
+                        include::esp_eap_ttls_phase2_types_ESP_EAP_TTLS_PHASE2_MSCHAPV2,
+                    ))?;
+                }
+                // PEAP carries EAP-MSCHAPv2 as its inner EAP method by default,
+                // which needs no TTLS phase-2 selection.
+                EapMethod::Peap => {}
+                // TLS authenticates with certificates and has no inner method.
+                EapMethod::Tls => {}
+            }
+
+            esp_wifi_result!(include::esp_wifi_sta_enterprise_enable())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Fine Timing Measurement (FTM) ranging.
+//!
+//! FTM (802.11mc) estimates the distance to a responder AP from the measured
+//! round-trip time of a burst of action frames. [`WifiController::start_ftm`]
+//! runs an initiator burst and resolves to an [`FtmReport`];
+//! [`WifiController::enable_ftm_responder`] turns an esp access point into a
+//! responder so two devices can range against each other.
+
+use alloc::vec::Vec;
+
+use super::{WifiController, WifiError};
+
+/// A single frame exchange within an FTM burst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FtmSample {
+    /// Measured round-trip time for this frame, in picoseconds.
+    pub rtt_ps: u32,
+    /// RSSI of the responding frame, in dBm.
+    pub rssi: i8,
+}
+
+/// The aggregated result of an FTM burst.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FtmReport {
+    /// Estimated round-trip time averaged over the burst, in picoseconds.
+    pub rtt_ps: u32,
+    /// Estimated distance derived from `rtt_ps`, in centimeters.
+    pub distance_cm: u32,
+    /// Per-frame samples, for the caller's own outlier filtering.
+    pub samples: Vec<FtmSample>,
+}
+
+/// Parameters for an FTM initiator burst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FtmConfig {
+    /// BSSID of the responder AP to range against.
+    pub peer_bssid: [u8; 6],
+    /// Channel the responder is on.
+    pub channel: u8,
+    /// Number of FTM frames requested in the burst.
+    pub frame_count: u8,
+    /// Spacing between bursts, in units of 100 milliseconds.
+    pub burst_period: u16,
+}
+
+impl WifiController<'_> {
+    /// Run an FTM burst against `config.peer_bssid` and resolve the ranging
+    /// report once the responder has completed the exchange.
+    pub async fn start_ftm(&mut self, config: &FtmConfig) -> Result<FtmReport, WifiError> {
+        // The bindgen sys struct has no `Default`; zero it before filling in
+        // the fields we set.
+        let mut cfg: include::wifi_ftm_initiator_cfg_t = unsafe { core::mem::zeroed() };
+        cfg.resp_mac = config.peer_bssid;
+        cfg.channel = config.channel;
+        cfg.frm_count = config.frame_count;
+        cfg.burst_period = config.burst_period;
+
+        unsafe {
+            esp_wifi_result!(include::esp_wifi_ftm_initiate_session(&mut cfg))?;
+        }
+
+        self.wait_for_ftm_report().await
+    }
+
+    /// Wait for the `FTM_REPORT` event and translate the driver payload into an
+    /// [`FtmReport`].
+    ///
+    /// The driver reports the averaged round-trip time and distance alongside a
+    /// pointer to the per-frame entry table, which is freed once this returns.
+    async fn wait_for_ftm_report(&mut self) -> Result<FtmReport, WifiError> {
+        let report = self.next_ftm_report_event().await?;
+        if report.status != include::wifi_ftm_status_t_FTM_STATUS_SUCCESS {
+            return Err(WifiError::Disconnected);
+        }
+
+        let entries =
+            unsafe { core::slice::from_raw_parts(report.ftm_report_data, report.ftm_report_num_entries as usize) };
+        let mut samples = Vec::with_capacity(entries.len());
+        for entry in entries {
+            samples.push(FtmSample {
+                rtt_ps: entry.rtt,
+                rssi: entry.rssi,
+            });
+        }
+
+        Ok(FtmReport {
+            // The event's `rtt_est` is in nanoseconds while the per-entry `rtt`
+            // is already in picoseconds; scale the aggregate so both fields
+            // share the documented picosecond unit.
+            rtt_ps: report.rtt_est.saturating_mul(1000),
+            distance_cm: report.dist_est,
+            samples,
+        })
+    }
+
+    /// Enable the FTM responder on the access-point interface so peers can
+    /// range against this device.
+    pub fn enable_ftm_responder(&mut self) -> Result<(), WifiError> {
+        unsafe {
+            esp_wifi_result!(include::esp_wifi_ftm_resp_set_offset(0))?;
+            esp_wifi_result!(include::esp_wifi_ftm_enable_responder(true))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Geolocation-friendly scan result export.
+//!
+//! WiFi-positioning resolvers expect a compact list of nearby BSSIDs with
+//! their signal strength. These helpers turn [`CachedScanResult`]s into
+//! [`GeolocationFingerprint`] entries - sorted strongest-first, de-duplicated,
+//! and optionally filtered of the entries that degrade a location fix - ready
+//! to serialize and POST to such a resolver.
+
+use alloc::vec::Vec;
+
+use super::background_scan::CachedScanResult;
+
+/// A single access point as seen by a geolocation resolver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GeolocationFingerprint {
+    /// BSSID of the access point.
+    pub bssid: [u8; 6],
+    /// Observed signal strength, in dBm.
+    pub rssi_dbm: i8,
+    /// Channel the AP was heard on.
+    pub channel: u8,
+}
+
+/// Controls which observations are excluded from a fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FingerprintFilter {
+    /// Drop hidden (empty SSID) networks, which rarely help a fix.
+    pub drop_hidden: bool,
+    /// Drop locally-administered BSSIDs (the I/G-adjacent U/L bit set), which
+    /// are typically randomized or virtual and pollute the lookup.
+    pub drop_locally_administered: bool,
+}
+
+impl Default for FingerprintFilter {
+    fn default() -> Self {
+        Self {
+            drop_hidden: true,
+            drop_locally_administered: true,
+        }
+    }
+}
+
+impl CachedScanResult {
+    /// Project this result into a [`GeolocationFingerprint`], unless `filter`
+    /// rejects it.
+    pub fn as_geolocation_fingerprint(
+        &self,
+        filter: &FingerprintFilter,
+    ) -> Option<GeolocationFingerprint> {
+        if filter.drop_hidden && self.ssid.is_empty() {
+            return None;
+        }
+        // The U/L bit is bit 1 of the first octet; set means locally
+        // administered.
+        if filter.drop_locally_administered && self.bssid[0] & 0x02 != 0 {
+            return None;
+        }
+        Some(GeolocationFingerprint {
+            bssid: self.bssid,
+            rssi_dbm: self.rssi,
+            channel: self.channel,
+        })
+    }
+}
+
+/// Build a fingerprint from a batch of scan results.
+///
+/// Entries are de-duplicated by BSSID (keeping the strongest signal) and
+/// returned sorted from strongest to weakest, the order positioning resolvers
+/// weight highest.
+pub fn collect_geolocation_fingerprint(
+    results: &[CachedScanResult],
+    filter: &FingerprintFilter,
+) -> Vec<GeolocationFingerprint> {
+    let mut out: Vec<GeolocationFingerprint> = Vec::new();
+    for result in results {
+        let Some(fp) = result.as_geolocation_fingerprint(filter) else {
+            continue;
+        };
+        match out.iter_mut().find(|e| e.bssid == fp.bssid) {
+            Some(existing) if existing.rssi_dbm < fp.rssi_dbm => *existing = fp,
+            Some(_) => {}
+            None => out.push(fp),
+        }
+    }
+    out.sort_unstable_by(|a, b| b.rssi_dbm.cmp(&a.rssi_dbm));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(bssid: [u8; 6], ssid: &str, rssi: i8) -> CachedScanResult {
+        CachedScanResult {
+            bssid,
+            ssid: heapless::String::try_from(ssid).unwrap(),
+            rssi,
+            channel: 1,
+            last_seen_ms: 0,
+        }
+    }
+
+    #[test]
+    fn collect_dedups_keeping_strongest_and_sorts_by_signal() {
+        let results = [
+            result([1, 1, 1, 1, 1, 1], "a", -70),
+            result([4, 4, 4, 4, 4, 4], "b", -40),
+            // Duplicate BSSID with a stronger reading than the first.
+            result([1, 1, 1, 1, 1, 1], "a", -55),
+        ];
+
+        let fp = collect_geolocation_fingerprint(&results, &FingerprintFilter::default());
+
+        // De-duplicated to two unique BSSIDs.
+        assert_eq!(fp.len(), 2);
+        // Sorted strongest-first.
+        assert_eq!(fp[0].bssid, [4, 4, 4, 4, 4, 4]);
+        assert_eq!(fp[0].rssi_dbm, -40);
+        // The stronger of the two duplicate readings was kept.
+        assert_eq!(fp[1].bssid, [1, 1, 1, 1, 1, 1]);
+        assert_eq!(fp[1].rssi_dbm, -55);
+    }
+
+    #[test]
+    fn collect_filters_hidden_and_locally_administered() {
+        let results = [
+            result([0, 0, 0, 0, 0, 1], "", -50), // hidden SSID
+            result([2, 0, 0, 0, 0, 1], "la", -50), // locally-administered (U/L bit set)
+            result([0, 0, 0, 0, 0, 2], "ok", -60),
+        ];
+
+        let fp = collect_geolocation_fingerprint(&results, &FingerprintFilter::default());
+
+        assert_eq!(fp.len(), 1);
+        assert_eq!(fp[0].bssid, [0, 0, 0, 0, 0, 2]);
+    }
+}
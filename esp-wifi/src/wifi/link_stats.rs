@@ -0,0 +1,38 @@
+//! Link-layer statistics query API.
+//!
+//! [`WifiController::link_layer_stats`] returns a snapshot of the connection
+//! health figures the public IDF API actually exposes - the associated channel
+//! and the station RSSI. The richer per-access-category MPDU counters and radio
+//! timing figures that vendor HALs publish have no accessor in esp-wifi-sys, so
+//! rather than ship fields that are permanently zero this snapshot carries only
+//! what can be populated; it will grow as the driver gains the counters.
+
+use super::{WifiController, WifiError};
+
+/// A snapshot of the link-layer statistics for the station interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkLayerStats {
+    /// Channel the station is currently associated on.
+    pub channel: u8,
+    /// Current station RSSI, in dBm.
+    pub rssi: i8,
+}
+
+impl WifiController<'_> {
+    /// Read a snapshot of the link-layer statistics for the station interface.
+    pub fn link_layer_stats(&self) -> Result<LinkLayerStats, WifiError> {
+        let mut rssi = 0i32;
+        let mut primary = 0u8;
+        let mut second = include::wifi_second_chan_t_WIFI_SECOND_CHAN_NONE;
+        unsafe {
+            esp_wifi_result!(include::esp_wifi_sta_get_rssi(&mut rssi))?;
+            esp_wifi_result!(include::esp_wifi_get_channel(&mut primary, &mut second))?;
+        }
+
+        Ok(LinkLayerStats {
+            channel: primary,
+            rssi: rssi as i8,
+        })
+    }
+}
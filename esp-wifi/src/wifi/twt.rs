@@ -0,0 +1,148 @@
+//! Target Wake Time (TWT) power-save negotiation.
+//!
+//! TWT lets a battery-powered station negotiate individual wake windows with
+//! the connected AP instead of waking on every DTIM beacon, cutting idle
+//! current on 802.11ax parts such as the ESP32-C6. [`WifiController::setup_twt`]
+//! starts the request/response handshake; because the AP assigns the flow id
+//! and final schedule asynchronously, the accepted parameters are delivered as
+//! a [`TwtEvent`] from [`WifiController::wait_for_twt_event`].
+
+use super::{WifiController, WifiError};
+
+/// Identifier assigned by the AP to an established TWT agreement.
+///
+/// Individual TWT uses flow IDs 0..=7.
+pub type TwtFlowId = u8;
+
+/// How the station is woken for each service period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TwtTriggerMode {
+    /// The AP sends a trigger frame at the start of every wake window.
+    Trigger,
+    /// The AP announces buffered traffic; the station polls when it wakes.
+    Announced,
+    /// The station wakes on schedule without any announcement.
+    Unannounced,
+}
+
+/// Parameters for an individual TWT agreement request.
+///
+/// The wake interval is expressed as `mantissa * 2^exponent` microseconds, the
+/// wire encoding used by the 802.11ax TWT element, so callers can request long
+/// sleep periods without overflowing a `u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TwtConfiguration {
+    /// Mantissa of the wake interval.
+    pub wake_interval_mantissa: u16,
+    /// Exponent of the wake interval.
+    pub wake_interval_exponent: u8,
+    /// Nominal minimum wake duration, in units of 256 microseconds.
+    pub min_wake_duration: u8,
+    /// Trigger / announcement behaviour for the agreement.
+    pub trigger: TwtTriggerMode,
+}
+
+impl Default for TwtConfiguration {
+    fn default() -> Self {
+        // ~5.24 s wake interval (80 * 2^16 us), 2 ms wake duration, announced.
+        Self {
+            wake_interval_mantissa: 80,
+            wake_interval_exponent: 16,
+            min_wake_duration: 8,
+            trigger: TwtTriggerMode::Announced,
+        }
+    }
+}
+
+impl TwtConfiguration {
+    /// Resolve the wake interval to microseconds.
+    pub fn wake_interval_us(&self) -> u64 {
+        (self.wake_interval_mantissa as u64) << self.wake_interval_exponent
+    }
+}
+
+/// An asynchronous TWT notification from the AP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TwtEvent {
+    /// An agreement was accepted. The flow id and the AP's accepted wake
+    /// schedule (which may differ from the request) are reported here.
+    Setup {
+        /// Flow id assigned by the AP.
+        flow_id: TwtFlowId,
+        /// Accepted wake interval, in microseconds.
+        wake_interval_us: u64,
+        /// Accepted minimum wake duration, in units of 256 microseconds.
+        min_wake_duration: u8,
+    },
+    /// An agreement was torn down, by either peer.
+    Teardown {
+        /// Flow id that was torn down.
+        flow_id: TwtFlowId,
+    },
+}
+
+impl WifiController<'_> {
+    /// Start negotiating an individual TWT agreement with the connected AP.
+    ///
+    /// The AP assigns the flow id and final schedule asynchronously, so this
+    /// only kicks off the handshake; await the outcome with
+    /// [`wait_for_twt_event`](Self::wait_for_twt_event).
+    pub fn setup_twt(&mut self, config: &TwtConfiguration) -> Result<(), WifiError> {
+        let mut setup = include::wifi_twt_setup_config_t {
+            setup_cmd: include::wifi_twt_setup_cmd_t_TWT_REQUEST,
+            flow_id: 0,
+            twt_id: 0,
+            min_wake_dura: config.min_wake_duration,
+            wake_invl_expn: config.wake_interval_exponent,
+            wake_invl_mant: config.wake_interval_mantissa,
+            trigger: matches!(config.trigger, TwtTriggerMode::Trigger),
+            flow_type: matches!(config.trigger, TwtTriggerMode::Unannounced) as u8,
+            ..unsafe { core::mem::zeroed() }
+        };
+
+        unsafe {
+            esp_wifi_result!(include::esp_wifi_sta_itwt_setup(&mut setup))?;
+        }
+
+        Ok(())
+    }
+
+    /// Tear down a previously negotiated TWT agreement. Completion is reported
+    /// as a [`TwtEvent::Teardown`].
+    pub fn teardown_twt(&mut self, flow_id: TwtFlowId) -> Result<(), WifiError> {
+        unsafe {
+            esp_wifi_result!(include::esp_wifi_sta_itwt_teardown(flow_id))?;
+        }
+        Ok(())
+    }
+
+    /// Wait for the next TWT notification from the AP.
+    ///
+    /// Resolves when the driver reports an `ITWT_SETUP` or `ITWT_TEARDOWN`
+    /// event, translating the already-decoded payload into a [`TwtEvent`].
+    pub async fn wait_for_twt_event(&mut self) -> Result<TwtEvent, WifiError> {
+        let event = match self.next_itwt_event().await? {
+            RawItwtEvent::Setup(s) => TwtEvent::Setup {
+                flow_id: s.flow_id,
+                wake_interval_us: (s.wake_invl_mant as u64) << s.wake_invl_expn,
+                min_wake_duration: s.min_wake_dura,
+            },
+            RawItwtEvent::Teardown(t) => TwtEvent::Teardown { flow_id: t.flow_id },
+        };
+        Ok(event)
+    }
+}
+
+/// An individual-TWT driver event, tagged by the event id the driver
+/// dispatched so its payload union is read as the matching variant.
+///
+/// The event loop builds this when it receives `WIFI_EVENT_ITWT_SETUP` /
+/// `WIFI_EVENT_ITWT_TEARDOWN`, pairing the id with the corresponding payload
+/// struct rather than conflating the two.
+pub(crate) enum RawItwtEvent {
+    Setup(include::wifi_event_sta_itwt_setup_t),
+    Teardown(include::wifi_event_sta_itwt_teardown_t),
+}
@@ -1,7 +1,7 @@
 //! Embassy access point
 //!
 //! - creates an open access-point with SSID `esp-wifi`
-//! - you can connect to it using a static IP in range 192.168.2.2 .. 192.168.2.255, gateway 192.168.2.1
+//! - hands out leases over DHCP and answers every DNS A query with the AP address, so joining phones/laptops reach the portal with zero manual IP setup
 //! - open http://192.168.2.1:8080/ in your browser - the example will perform an HTTP get request to some "random" server
 //!
 //! On Android you might need to choose _Keep Accesspoint_ when it tells you the WiFi has no internet connection, Chrome might not want to load the URL - you can use a shell and try `curl` and `ping`
@@ -18,6 +18,7 @@
 use embassy_executor::Spawner;
 use embassy_net::{
     tcp::TcpSocket,
+    udp::{PacketMetadata, UdpSocket},
     IpListenEndpoint,
     Ipv4Address,
     Ipv4Cidr,
@@ -32,6 +33,7 @@ use esp_hal::{prelude::*, rng::Rng, timer::timg::TimerGroup};
 use esp_println::{print, println};
 use esp_wifi::{
     init,
+    utils::captive_portal::{CaptivePortalDns, DhcpServer, DhcpServerConfig},
     wifi::{
         AccessPointConfiguration,
         Configuration,
@@ -111,6 +113,8 @@ async fn main(spawner: Spawner) -> ! {
 
     spawner.spawn(connection(controller)).ok();
     spawner.spawn(net_task(&stack)).ok();
+    spawner.spawn(dhcp_server(&stack)).ok();
+    spawner.spawn(dns_server(&stack)).ok();
 
     let mut rx_buffer = [0; 1536];
     let mut tx_buffer = [0; 1536];
@@ -122,7 +126,7 @@ async fn main(spawner: Spawner) -> ! {
         Timer::after(Duration::from_millis(500)).await;
     }
     println!("Connect to the AP `esp-wifi` and point your browser to http://192.168.2.1:8080/");
-    println!("Use a static IP in the range 192.168.2.2 .. 192.168.2.255, use gateway 192.168.2.1");
+    println!("Your device will be leased an address automatically over DHCP");
 
     let mut socket = TcpSocket::new(&stack, &mut rx_buffer, &mut tx_buffer);
     socket.set_timeout(Some(embassy_time::Duration::from_secs(10)));
@@ -229,3 +233,43 @@ async fn connection(mut controller: WifiController<'static>) {
 async fn net_task(stack: &'static Stack<WifiDevice<'static, WifiApDevice>>) {
     stack.run().await
 }
+
+#[embassy_executor::task]
+async fn dhcp_server(stack: &'static Stack<WifiDevice<'static, WifiApDevice>>) {
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0; 1536];
+    let mut tx_buffer = [0; 1536];
+
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket.bind(67).unwrap();
+
+    let mut server = DhcpServer::new(DhcpServerConfig::default());
+    server.run(&mut socket).await
+}
+
+#[embassy_executor::task]
+async fn dns_server(stack: &'static Stack<WifiDevice<'static, WifiApDevice>>) {
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0; 512];
+    let mut tx_buffer = [0; 512];
+
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket.bind(53).unwrap();
+
+    let dns = CaptivePortalDns::new(Ipv4Address::new(192, 168, 2, 1));
+    dns.run(&mut socket).await
+}